@@ -0,0 +1,126 @@
+use bevy_ecs::resource::Resource;
+use bevy_reflect::Reflect;
+
+use super::ChunkMeshKey;
+
+/// How many bytes [`super::TilemapChunkMeshCache`] and the streamed-chunk
+/// asset pool are each allowed to hold onto before their least-recently-used
+/// entries are evicted. The budget applies independently to each of the two
+/// caches, not to their sum.
+///
+/// Defaults to 64 MiB, which is generous for the small per-tile meshes chunk
+/// rendering produces but still bounds long sessions that cycle through many
+/// distinct chunk sizes/topologies.
+#[derive(Resource, Clone, Copy, Reflect)]
+#[reflect(Resource)]
+pub struct ChunkCacheBudget {
+    /// The approximate byte budget applied separately to cached meshes and to
+    /// recycled streamed-chunk assets.
+    pub max_bytes: u64,
+}
+
+impl Default for ChunkCacheBudget {
+    fn default() -> Self {
+        Self {
+            max_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// Counts how many times the chunk cache systems have run, used as a cheap
+/// recency clock for LRU eviction instead of wall-clock time.
+#[derive(Resource, Default)]
+pub(super) struct ChunkCacheFrame(pub u64);
+
+/// A cached value alongside the bookkeeping needed to evict it under memory
+/// pressure: its approximate size and the frame it was last reused on.
+#[derive(Clone)]
+pub(super) struct CacheEntry<T> {
+    pub value: T,
+    pub approx_bytes: u64,
+    pub last_used_frame: u64,
+}
+
+/// A point-in-time report of how much memory the chunk caches are using,
+/// queryable by tools/diagnostics that want to watch for leaks.
+#[derive(Resource, Default, Clone, Copy, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ChunkCacheReport {
+    /// Entries and bytes held by [`super::TilemapChunkMeshCache`].
+    pub mesh_cache: ChunkCacheCategoryReport,
+    /// Entries and bytes held by recycled (despawned-but-pooled) streamed
+    /// chunk assets.
+    pub recycled_pool: ChunkCacheCategoryReport,
+}
+
+/// Entry and byte counts for one category of [`ChunkCacheReport`].
+#[derive(Default, Clone, Copy, Reflect)]
+pub struct ChunkCacheCategoryReport {
+    /// Number of cached entries in this category.
+    pub entry_count: usize,
+    /// Approximate total bytes held by this category's entries.
+    pub approx_bytes: u64,
+}
+
+impl ChunkCacheCategoryReport {
+    fn record<T>(&mut self, entry: &CacheEntry<T>) {
+        self.entry_count += 1;
+        self.approx_bytes += entry.approx_bytes;
+    }
+}
+
+/// Approximate bytes the tile-data image of a `tile_count`-tile chunk occupies:
+/// one [`super::PackedTileData`] per tile.
+pub(super) fn tile_data_image_bytes(tile_count: u64) -> u64 {
+    tile_count * core::mem::size_of::<super::PackedTileData>() as u64
+}
+
+/// Approximate bytes a chunk mesh occupies: one set of position, UV, and
+/// index data per tile, regardless of topology.
+pub(super) fn chunk_mesh_bytes(tile_count: u64) -> u64 {
+    const BYTES_PER_TILE: u64 = (3 * 4 + 2 * 4) * 6 + 12 * 4; // up to 6 verts + 12 indices (hex)
+    tile_count * BYTES_PER_TILE
+}
+
+/// Evicts least-recently-used entries from `entries` until their combined
+/// `approx_bytes` is within `max_bytes`, skipping anything used on the
+/// current frame (so a cache miss can't immediately evict the entry it just
+/// inserted) or present in `live`, which callers populate with keys still
+/// referenced by entities that exist right now.
+pub(super) fn evict_lru<K: Clone + core::hash::Hash + Eq, V>(
+    entries: &mut bevy_platform::collections::HashMap<K, CacheEntry<V>>,
+    live: &bevy_platform::collections::HashSet<K>,
+    max_bytes: u64,
+    current_frame: u64,
+) {
+    let mut total_bytes: u64 = entries.values().map(|entry| entry.approx_bytes).sum();
+    if total_bytes <= max_bytes {
+        return;
+    }
+
+    let mut by_age: Vec<(K, u64)> = entries
+        .iter()
+        .filter(|(key, entry)| entry.last_used_frame != current_frame && !live.contains(key))
+        .map(|(key, entry)| (key.clone(), entry.last_used_frame))
+        .collect();
+    by_age.sort_by_key(|(_, last_used_frame)| *last_used_frame);
+
+    for (key, _) in by_age {
+        if total_bytes <= max_bytes {
+            break;
+        }
+        if let Some(entry) = entries.remove(&key) {
+            total_bytes = total_bytes.saturating_sub(entry.approx_bytes);
+        }
+    }
+}
+
+pub(super) fn report_category<V>(
+    entries: &bevy_platform::collections::HashMap<ChunkMeshKey, CacheEntry<V>>,
+) -> ChunkCacheCategoryReport {
+    let mut report = ChunkCacheCategoryReport::default();
+    for entry in entries.values() {
+        report.record(entry);
+    }
+    report
+}
@@ -0,0 +1,113 @@
+use bevy_ecs::component::Component;
+use bevy_reflect::Reflect;
+
+use super::PackedTileData;
+
+/// Above this fraction of a chunk's tiles changing in one update, it's cheaper
+/// to re-pack and re-upload the whole tile-data image than to compute and copy
+/// a dirty span.
+const FULL_REBUILD_THRESHOLD: f32 = 0.5;
+
+/// The last [`PackedTileData`] uploaded for a chunk's tile-data image, kept
+/// around so [`super::update_tilemap_chunk_indices`] can diff against it and
+/// upload only the tiles that actually changed.
+#[derive(Component, Reflect)]
+pub(super) struct ChunkTileDataSnapshot(pub Vec<PackedTileData>);
+
+/// The span of linear tile indices that differ between `previous` and
+/// `current`, or `None` if nothing changed.
+pub(super) struct DirtySpan {
+    /// Index of the first changed tile.
+    pub start: usize,
+    /// Index one past the last changed tile.
+    pub end: usize,
+}
+
+/// Computes the minimal contiguous run covering every index at which
+/// `previous` and `current` differ.
+///
+/// Returns `None` if the two slices are identical, or if their lengths differ
+/// (a chunk resize always forces a full rebuild).
+pub(super) fn dirty_span(
+    previous: &[PackedTileData],
+    current: &[PackedTileData],
+) -> Option<DirtySpan> {
+    if previous.len() != current.len() {
+        return None;
+    }
+
+    let differs = |(old, new): (&PackedTileData, &PackedTileData)| {
+        bytemuck::bytes_of(old) != bytemuck::bytes_of(new)
+    };
+
+    let start = previous.iter().zip(current).position(differs)?;
+    let end = previous
+        .iter()
+        .zip(current)
+        .rposition(differs)
+        .map_or(start, |index| index + 1);
+
+    Some(DirtySpan { start, end })
+}
+
+/// Whether a [`DirtySpan`] touches enough of a `total_len`-tile chunk that a
+/// full rebuild is cheaper than a targeted copy.
+pub(super) fn should_full_rebuild(span: &DirtySpan, total_len: usize) -> bool {
+    total_len == 0 || (span.end - span.start) as f32 / total_len as f32 > FULL_REBUILD_THRESHOLD
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::TileRenderData;
+
+    fn tiles(indices: &[u16]) -> Vec<PackedTileData> {
+        indices
+            .iter()
+            .map(|&tileset_index| TileRenderData::from_tileset_index(tileset_index).into())
+            .collect()
+    }
+
+    #[test]
+    fn dirty_span_is_none_for_identical_slices() {
+        let previous = tiles(&[1, 2, 3, 4]);
+        let current = previous.clone();
+        assert!(dirty_span(&previous, &current).is_none());
+    }
+
+    #[test]
+    fn dirty_span_covers_a_single_changed_tile() {
+        let previous = tiles(&[1, 2, 3, 4]);
+        let current = tiles(&[1, 2, 9, 4]);
+        let span = dirty_span(&previous, &current).unwrap();
+        assert_eq!(span.start, 2);
+        assert_eq!(span.end, 3);
+    }
+
+    #[test]
+    fn dirty_span_is_none_for_mismatched_lengths() {
+        // A resized chunk falls back to a full rebuild the same way an
+        // over-threshold dirty span would, via the `None` returned here.
+        let previous = tiles(&[1, 2, 3]);
+        let current = tiles(&[1, 2, 3, 4]);
+        assert!(dirty_span(&previous, &current).is_none());
+    }
+
+    #[test]
+    fn small_span_does_not_force_a_full_rebuild() {
+        let span = DirtySpan { start: 2, end: 3 };
+        assert!(!should_full_rebuild(&span, 10));
+    }
+
+    #[test]
+    fn large_span_forces_a_full_rebuild() {
+        let span = DirtySpan { start: 0, end: 9 };
+        assert!(should_full_rebuild(&span, 10));
+    }
+
+    #[test]
+    fn empty_chunk_forces_a_full_rebuild() {
+        let span = DirtySpan { start: 0, end: 0 };
+        assert!(should_full_rebuild(&span, 0));
+    }
+}
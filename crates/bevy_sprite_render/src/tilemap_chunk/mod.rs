@@ -4,7 +4,7 @@ use crate::{AlphaMode2d, MeshMaterial2d};
 use bevy_app::{App, Plugin, Update};
 use bevy_asset::{Assets, Handle};
 use bevy_color::Color;
-use bevy_derive::{Deref, DerefMut};
+use bevy_derive::Deref;
 use bevy_ecs::{
     component::Component,
     entity::Entity,
@@ -14,22 +14,37 @@ use bevy_ecs::{
     reflect::{ReflectComponent, ReflectResource},
     relationship::Relationship,
     resource::Resource,
-    system::{Commands, Query, ResMut},
+    system::{Commands, Query, Res, ResMut},
     world::DeferredWorld,
 };
 use bevy_image::Image;
-use bevy_math::{primitives::Rectangle, UVec2};
+use bevy_math::UVec2;
 use bevy_mesh::{Mesh, Mesh2d};
-use bevy_platform::collections::HashMap;
+use bevy_platform::collections::{HashMap, HashSet};
 use bevy_reflect::{prelude::*, Reflect};
 use bevy_sprite::{TileData, TileStorage, Tilemap};
 use bevy_transform::components::Transform;
 use bevy_utils::default;
 use tracing::warn;
 
+mod cache_budget;
+mod dirty;
+mod streaming;
+mod tiled_loader;
 mod tilemap_chunk_material;
+mod tileset_builder;
+mod topology;
 
+use cache_budget::{chunk_mesh_bytes, evict_lru, report_category, CacheEntry, ChunkCacheFrame};
+use dirty::{dirty_span, should_full_rebuild, ChunkTileDataSnapshot};
+use streaming::ChunkAssetPool;
+use topology::build_chunk_mesh;
+pub use cache_budget::{ChunkCacheBudget, ChunkCacheCategoryReport, ChunkCacheReport};
+pub use streaming::{AutoChunk, ChunkPopulateInput, STREAM_CHUNK_SIZE};
+pub use tiled_loader::*;
 pub use tilemap_chunk_material::*;
+pub use tileset_builder::*;
+pub use topology::TilemapTopology;
 
 /// Plugin that handles the initialization and updating of tilemap chunks.
 /// Adds systems for processing newly added tilemap chunks and updating their indices.
@@ -37,15 +52,70 @@ pub struct TilemapChunkPlugin;
 
 impl Plugin for TilemapChunkPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<TilemapChunkMeshCache>()
-            .add_systems(Update, update_tilemap_chunk_indices);
+        app.init_asset::<TiledMap>()
+            .init_asset_loader::<TiledMapLoader>()
+            .init_resource::<TilemapChunkMeshCache>()
+            .init_resource::<TilesetBuilderCache>()
+            .init_resource::<ChunkAssetPool>()
+            .init_resource::<ChunkCacheBudget>()
+            .init_resource::<ChunkCacheReport>()
+            .init_resource::<ChunkCacheFrame>()
+            .add_systems(
+                Update,
+                (
+                    advance_chunk_cache_frame,
+                    tileset_builder::assemble_loose_tilesets,
+                    spawn_tiled_maps,
+                    streaming::stream_tilemap_chunks,
+                    update_tilemap_chunk_indices,
+                    evict_chunk_caches,
+                )
+                    .chain(),
+            );
     }
 }
 
-/// A resource storing the meshes for each tilemap chunk size.
-#[derive(Resource, Default, Deref, DerefMut, Reflect)]
-#[reflect(Resource, Default)]
-pub struct TilemapChunkMeshCache(HashMap<UVec2, Handle<Mesh>>);
+/// The key [`TilemapChunkMeshCache`] indexes cached chunk meshes by.
+///
+/// Includes the topology alongside the chunk's tile grid dimensions and
+/// per-tile display size, since those three together fully determine the
+/// generated mesh's vertex layout; meshes are never shared across topologies.
+pub type ChunkMeshKey = (TilemapTopology, UVec2, UVec2);
+
+/// A resource storing the meshes for each tilemap chunk size, evicting
+/// least-recently-used entries once [`ChunkCacheBudget`] is exceeded.
+#[derive(Resource, Default)]
+pub struct TilemapChunkMeshCache {
+    entries: HashMap<ChunkMeshKey, CacheEntry<Handle<Mesh>>>,
+}
+
+impl TilemapChunkMeshCache {
+    /// Returns the cached mesh for `key`, if any, marking it used on
+    /// `current_frame` so it survives the next LRU eviction pass.
+    fn get(&mut self, key: &ChunkMeshKey, current_frame: u64) -> Option<Handle<Mesh>> {
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used_frame = current_frame;
+        Some(entry.value.clone())
+    }
+
+    /// Caches `mesh` under `key`, sized for a chunk of `tile_count` tiles.
+    fn insert(
+        &mut self,
+        key: ChunkMeshKey,
+        mesh: Handle<Mesh>,
+        tile_count: u64,
+        current_frame: u64,
+    ) {
+        self.entries.insert(
+            key,
+            CacheEntry {
+                value: mesh,
+                approx_bytes: chunk_mesh_bytes(tile_count),
+                last_used_frame: current_frame,
+            },
+        );
+    }
+}
 
 /// Information for rendering chunks in a tilemap
 #[derive(Component, Clone, Debug, Default, Reflect)]
@@ -57,6 +127,8 @@ pub struct TilemapChunkRenderer {
     pub tileset: Handle<Image>,
     /// The alpha mode to use for the tilemap chunk.
     pub alpha_mode: AlphaMode2d,
+    /// The grid layout tiles in this chunk are arranged in.
+    pub topology: TilemapTopology,
 }
 
 /// Data for a single tile in the tilemap chunk.
@@ -100,6 +172,7 @@ fn update_tilemap_chunk_indices(
             &ChildOf,
             &TileStorage<TileRenderData>,
             Option<&MeshMaterial2d<TilemapChunkMaterial>>,
+            Option<&ChunkTileDataSnapshot>,
         ),
         Changed<TileStorage<TileRenderData>>,
     >,
@@ -108,9 +181,11 @@ fn update_tilemap_chunk_indices(
     mut materials: ResMut<Assets<TilemapChunkMaterial>>,
     mut images: ResMut<Assets<Image>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    cache_frame: Res<ChunkCacheFrame>,
     mut commands: Commands,
 ) {
-    for (chunk_id, in_map, storage, material) in query {
+    let current_frame = cache_frame.0;
+    for (chunk_id, in_map, storage, material, snapshot) in query {
         let Ok((map, map_renderer)) = map_query.get(in_map.get()) else {
             warn!(
                 "Could not find Tilemap {} for chunk {}",
@@ -139,18 +214,42 @@ fn update_tilemap_chunk_indices(
                 );
                 continue;
             };
-            data.clear();
-            data.extend_from_slice(bytemuck::cast_slice(&packed_tile_data));
+
+            let span = snapshot.and_then(|snapshot| dirty_span(&snapshot.0, &packed_tile_data));
+            match span {
+                Some(span) if !should_full_rebuild(&span, packed_tile_data.len()) => {
+                    let tile_size = core::mem::size_of::<PackedTileData>();
+                    let byte_start = span.start * tile_size;
+                    let byte_end = span.end * tile_size;
+                    let dirty_bytes =
+                        bytemuck::cast_slice(&packed_tile_data[span.start..span.end]);
+                    data[byte_start..byte_end].copy_from_slice(dirty_bytes);
+                }
+                _ => {
+                    data.clear();
+                    data.extend_from_slice(bytemuck::cast_slice(&packed_tile_data));
+                }
+            }
+
+            commands
+                .entity(chunk_id)
+                .insert(ChunkTileDataSnapshot(packed_tile_data));
         } else {
             let tile_data_image = make_chunk_tile_data_image(&storage.size, &packed_tile_data);
 
-            let mesh_size = storage.size * map.tile_display_size;
+            let mesh_key: ChunkMeshKey =
+                (map_renderer.topology, storage.size, map.tile_display_size);
 
-            let mesh = if let Some(mesh) = tilemap_chunk_mesh_cache.get(&mesh_size) {
-                mesh.clone()
+            let mesh = if let Some(mesh) = tilemap_chunk_mesh_cache.get(&mesh_key, current_frame) {
+                mesh
             } else {
-                let mesh = meshes.add(Rectangle::from_size(mesh_size.as_vec2()));
-                tilemap_chunk_mesh_cache.insert(mesh_size, mesh.clone());
+                let mesh = meshes.add(build_chunk_mesh(
+                    map_renderer.topology,
+                    storage.size,
+                    map.tile_display_size,
+                ));
+                let tile_count = (storage.size.x * storage.size.y) as u64;
+                tilemap_chunk_mesh_cache.insert(mesh_key, mesh.clone(), tile_count, current_frame);
                 mesh
             };
             let tile_data = images.add(tile_data_image);
@@ -161,9 +260,55 @@ fn update_tilemap_chunk_indices(
                 alpha_mode: map_renderer.alpha_mode,
             });
 
-            commands
-                .entity(chunk_id)
-                .insert((Mesh2d(mesh), MeshMaterial2d(material)));
+            commands.entity(chunk_id).insert((
+                Mesh2d(mesh),
+                MeshMaterial2d(material),
+                ChunkTileDataSnapshot(packed_tile_data),
+            ));
         };
     }
 }
+
+/// Advances the chunk cache recency clock. Runs before any system that
+/// stamps a cache entry's `last_used_frame`, so entries inserted or reused
+/// this tick are stamped with the same frame `evict_chunk_caches` later
+/// compares against, rather than the previous tick's (which made freshly
+/// inserted entries immediately evictable).
+fn advance_chunk_cache_frame(mut frame: ResMut<ChunkCacheFrame>) {
+    frame.0 += 1;
+}
+
+/// Evicts least-recently-used entries from [`TilemapChunkMeshCache`] and the
+/// streamed-chunk asset pool once [`ChunkCacheBudget`] is exceeded,
+/// publishing the result to [`ChunkCacheReport`]. Mesh cache entries still
+/// keyed by a live chunk are never evicted: dropping them wouldn't free the
+/// mesh (the chunk's own [`Mesh2d`] keeps it alive) and would just force a
+/// duplicate rebuild the next time a chunk of that shape is processed.
+fn evict_chunk_caches(
+    budget: Res<ChunkCacheBudget>,
+    frame: Res<ChunkCacheFrame>,
+    mut mesh_cache: ResMut<TilemapChunkMeshCache>,
+    mut pool: ResMut<ChunkAssetPool>,
+    mut report: ResMut<ChunkCacheReport>,
+    chunks: Query<(&ChildOf, &TileStorage<TileRenderData>)>,
+    map_query: Query<(&Tilemap, &TilemapChunkRenderer)>,
+) {
+    let live_mesh_keys: HashSet<ChunkMeshKey> = chunks
+        .iter()
+        .filter_map(|(in_map, storage)| {
+            let (map, renderer) = map_query.get(in_map.get()).ok()?;
+            Some((renderer.topology, storage.size, map.tile_display_size))
+        })
+        .collect();
+
+    evict_lru(
+        &mut mesh_cache.entries,
+        &live_mesh_keys,
+        budget.max_bytes,
+        frame.0,
+    );
+    pool.evict(budget.max_bytes, frame.0);
+
+    report.mesh_cache = report_category(&mesh_cache.entries);
+    report.recycled_pool = pool.report();
+}
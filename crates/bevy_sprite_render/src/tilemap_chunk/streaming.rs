@@ -0,0 +1,273 @@
+use bevy_asset::Assets;
+use bevy_camera::Camera;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    hierarchy::ChildOf,
+    query::With,
+    resource::Resource,
+    system::{Commands, In, Query, Res, ResMut, SystemId},
+};
+use bevy_math::{IVec2, UVec2, Vec2};
+use bevy_mesh::Mesh2d;
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_sprite::Tilemap;
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::MeshMaterial2d;
+
+use super::cache_budget::{
+    chunk_mesh_bytes, tile_data_image_bytes, CacheEntry, ChunkCacheCategoryReport, ChunkCacheFrame,
+};
+use super::{ChunkMeshKey, TilemapChunkMaterial, TilemapChunkRenderer};
+
+/// The size, in tiles, of each chunk an [`AutoChunk`] map streams in and out.
+pub const STREAM_CHUNK_SIZE: UVec2 = UVec2::splat(32);
+
+/// Input passed to an [`AutoChunk::populate`] one-shot system when a new
+/// chunk streams into range.
+///
+/// The system is expected to `insert` a `TileStorage<TileRenderData>` onto
+/// `chunk_entity` to give the newly streamed-in chunk its tiles; doing so is
+/// what causes `update_tilemap_chunk_indices` to pick the chunk up.
+#[derive(Clone, Copy)]
+pub struct ChunkPopulateInput {
+    /// The freshly spawned, as-yet tileless chunk entity.
+    pub chunk_entity: Entity,
+    /// This chunk's coordinate in the map's chunk grid.
+    pub coords: IVec2,
+}
+
+/// Add this to a [`Tilemap`] entity to stream its chunks in and out around
+/// active cameras instead of requiring every chunk to be spawned up front.
+///
+/// Chunks within `spawn_radius` (world units) of any camera are spawned on
+/// demand via `populate`; chunks further than `despawn_radius` from every
+/// camera are despawned and their mesh and material (and thus tile-data
+/// image) handles are returned to a pool for reuse by the next chunk that
+/// streams in with the same mesh shape.
+#[derive(Component)]
+pub struct AutoChunk {
+    /// Chunks within this distance of a camera are spawned.
+    pub spawn_radius: f32,
+    /// Chunks further than this distance from every camera are despawned.
+    /// Should be `>= spawn_radius` so a camera sitting near the boundary
+    /// doesn't spawn and despawn the same chunk every frame.
+    pub despawn_radius: f32,
+    /// One-shot system that populates a newly streamed chunk's tiles.
+    pub populate: SystemId<In<ChunkPopulateInput>>,
+}
+
+/// Marks a chunk entity as owned by its map's streaming system, recording its
+/// position in the map's chunk grid.
+#[derive(Component)]
+struct StreamedChunk {
+    coords: IVec2,
+}
+
+/// Mesh and material handles handed back by despawned streamed chunks, ready
+/// to be reused by the next streamed-in chunk of the same shape instead of
+/// allocating fresh `Assets<Mesh>`/`Assets<TilemapChunkMaterial>` (and, via
+/// the material, a fresh tile-data `Assets<Image>`) entries.
+///
+/// Entries are evicted longest-pooled-first once
+/// [`super::ChunkCacheBudget`] is exceeded, by `evict_chunk_caches`.
+#[derive(Resource, Default)]
+pub(super) struct ChunkAssetPool {
+    recycled:
+        HashMap<ChunkMeshKey, Vec<CacheEntry<(Mesh2d, MeshMaterial2d<TilemapChunkMaterial>)>>>,
+}
+
+impl ChunkAssetPool {
+    fn pop(
+        &mut self,
+        key: &ChunkMeshKey,
+    ) -> Option<(Mesh2d, MeshMaterial2d<TilemapChunkMaterial>)> {
+        let entry = self.recycled.get_mut(key)?.pop()?;
+        Some(entry.value)
+    }
+
+    fn push(
+        &mut self,
+        key: ChunkMeshKey,
+        value: (Mesh2d, MeshMaterial2d<TilemapChunkMaterial>),
+        tile_count: u64,
+        current_frame: u64,
+    ) {
+        self.recycled.entry(key).or_default().push(CacheEntry {
+            value,
+            approx_bytes: chunk_mesh_bytes(tile_count) + tile_data_image_bytes(tile_count),
+            last_used_frame: current_frame,
+        });
+    }
+
+    /// Evicts the longest-pooled chunks until the pool's total approximate
+    /// size is within `max_bytes`, skipping anything pooled this very frame.
+    /// A chunk taken back out of the pool is no longer in it.
+    pub(super) fn evict(&mut self, max_bytes: u64, current_frame: u64) {
+        let mut all: Vec<_> = self
+            .recycled
+            .drain()
+            .flat_map(|(key, entries)| entries.into_iter().map(move |entry| (key, entry)))
+            .collect();
+        all.sort_by_key(|(_, entry)| entry.last_used_frame);
+
+        let mut total_bytes: u64 = all.iter().map(|(_, entry)| entry.approx_bytes).sum();
+        let mut kept = Vec::with_capacity(all.len());
+        for (key, entry) in all.drain(..) {
+            if total_bytes > max_bytes && entry.last_used_frame != current_frame {
+                total_bytes = total_bytes.saturating_sub(entry.approx_bytes);
+                continue;
+            }
+            kept.push((key, entry));
+        }
+
+        for (key, entry) in kept {
+            self.recycled.entry(key).or_default().push(entry);
+        }
+    }
+
+    pub(super) fn report(&self) -> ChunkCacheCategoryReport {
+        let mut report = ChunkCacheCategoryReport::default();
+        for entry in self.recycled.values().flatten() {
+            report.entry_count += 1;
+            report.approx_bytes += entry.approx_bytes;
+        }
+        report
+    }
+}
+
+pub(super) fn stream_tilemap_chunks(
+    cameras: Query<&GlobalTransform, With<Camera>>,
+    maps: Query<(Entity, &Tilemap, &TilemapChunkRenderer, &AutoChunk)>,
+    existing_chunks: Query<(Entity, &ChildOf, &StreamedChunk)>,
+    mut pool: ResMut<ChunkAssetPool>,
+    mut materials: ResMut<Assets<TilemapChunkMaterial>>,
+    cache_frame: Res<ChunkCacheFrame>,
+    mut commands: Commands,
+) {
+    let current_frame = cache_frame.0;
+    let camera_positions: Vec<Vec2> = cameras.iter().map(|t| t.translation().truncate()).collect();
+    if camera_positions.is_empty() {
+        return;
+    }
+
+    let mut spawned_by_map: HashMap<Entity, HashSet<IVec2>> = HashMap::default();
+    for (_, parent, streamed) in &existing_chunks {
+        spawned_by_map
+            .entry(parent.get())
+            .or_default()
+            .insert(streamed.coords);
+    }
+
+    for (map_id, map, renderer, auto_chunk) in &maps {
+        let chunk_span = STREAM_CHUNK_SIZE.as_vec2() * map.tile_display_size.as_vec2();
+        let mesh_key: ChunkMeshKey = (renderer.topology, STREAM_CHUNK_SIZE, map.tile_display_size);
+        let spawned = spawned_by_map.entry(map_id).or_default();
+
+        let wanted = wanted_chunk_coords(&camera_positions, chunk_span, auto_chunk.spawn_radius);
+        for coords in wanted {
+            if !spawned.insert(coords) {
+                continue;
+            }
+
+            let chunk_offset = coords.as_vec2() * chunk_span;
+            let mut chunk = commands.spawn((
+                ChildOf(map_id),
+                StreamedChunk { coords },
+                Transform::from_translation(chunk_offset.extend(0.0)),
+            ));
+
+            if let Some((mesh, material)) = pool.pop(&mesh_key) {
+                if let Some(material_asset) = materials.get_mut(material.id()) {
+                    material_asset.tileset = renderer.tileset.clone();
+                    material_asset.alpha_mode = renderer.alpha_mode;
+                }
+                chunk.insert((mesh, material));
+            }
+
+            let chunk_entity = chunk.id();
+            commands.run_system_with(
+                auto_chunk.populate,
+                ChunkPopulateInput {
+                    chunk_entity,
+                    coords,
+                },
+            );
+        }
+    }
+
+    for (chunk_id, parent, streamed) in &existing_chunks {
+        let Ok((_, map, renderer, auto_chunk)) = maps.get(parent.get()) else {
+            continue;
+        };
+        let chunk_span = STREAM_CHUNK_SIZE.as_vec2() * map.tile_display_size.as_vec2();
+        let world = chunk_world_center(streamed.coords, chunk_span);
+        let nearest = camera_positions
+            .iter()
+            .map(|camera_pos| camera_pos.distance(world))
+            .fold(f32::INFINITY, f32::min);
+
+        if nearest > auto_chunk.despawn_radius {
+            despawn_and_recycle(&mut commands, chunk_id, renderer, map, current_frame);
+        }
+    }
+}
+
+fn despawn_and_recycle(
+    commands: &mut Commands,
+    chunk_id: Entity,
+    renderer: &TilemapChunkRenderer,
+    map: &Tilemap,
+    current_frame: u64,
+) {
+    let mesh_key: ChunkMeshKey = (renderer.topology, STREAM_CHUNK_SIZE, map.tile_display_size);
+    let tile_count = (STREAM_CHUNK_SIZE.x * STREAM_CHUNK_SIZE.y) as u64;
+    commands.queue(move |world: &mut bevy_ecs::world::World| {
+        let Ok(entity) = world.get_entity(chunk_id) else {
+            return;
+        };
+        let mesh = entity.get::<Mesh2d>().cloned();
+        let material = entity
+            .get::<MeshMaterial2d<TilemapChunkMaterial>>()
+            .cloned();
+        if let (Some(mesh), Some(material)) = (mesh, material) {
+            world.resource_mut::<ChunkAssetPool>().push(
+                mesh_key,
+                (mesh, material),
+                tile_count,
+                current_frame,
+            );
+        }
+    });
+    commands.entity(chunk_id).despawn();
+}
+
+/// The world-space center of chunk `coords`, approximating every chunk as
+/// occupying a `chunk_span`-sized cell on a regular grid regardless of
+/// topology; close enough for streaming radius checks.
+fn chunk_world_center(coords: IVec2, chunk_span: Vec2) -> Vec2 {
+    coords.as_vec2() * chunk_span + chunk_span * 0.5
+}
+
+fn wanted_chunk_coords(
+    camera_positions: &[Vec2],
+    chunk_span: Vec2,
+    spawn_radius: f32,
+) -> HashSet<IVec2> {
+    let chunk_radius = (spawn_radius / chunk_span.min_element()).ceil() as i32 + 1;
+
+    let mut wanted = HashSet::default();
+    for camera_pos in camera_positions {
+        let center_chunk = (*camera_pos / chunk_span).floor().as_ivec2();
+        for dy in -chunk_radius..=chunk_radius {
+            for dx in -chunk_radius..=chunk_radius {
+                let coords = center_chunk + IVec2::new(dx, dy);
+                if camera_pos.distance(chunk_world_center(coords, chunk_span)) <= spawn_radius {
+                    wanted.insert(coords);
+                }
+            }
+        }
+    }
+    wanted
+}
@@ -0,0 +1,266 @@
+use core::fmt;
+
+use crate::AlphaMode2d;
+use bevy_asset::{io::Reader, Asset, AssetLoader, Assets, Handle, LoadContext};
+use bevy_color::Color;
+use bevy_derive::Deref;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    hierarchy::ChildOf,
+    query::Without,
+    system::{Commands, Query, Res},
+};
+use bevy_image::Image;
+use bevy_math::UVec2;
+use bevy_reflect::{prelude::*, TypePath};
+use bevy_sprite::{TileStorage, Tilemap};
+use bevy_transform::components::Transform;
+use bevy_utils::default;
+use thiserror::Error;
+
+use super::{TileRenderData, TilemapChunkRenderer};
+
+/// The size, in tiles, of each chunk spawned for a loaded Tiled map.
+///
+/// This matches the granularity [`TilemapChunkMeshCache`](super::TilemapChunkMeshCache)
+/// keys on, so a single large Tiled layer is split into several
+/// [`TileStorage<TileRenderData>`] chunks rather than one oversized chunk.
+pub const TILED_CHUNK_SIZE: UVec2 = UVec2::splat(32);
+
+/// The world-space Z distance between successive Tiled layers, so later
+/// layers draw on top of earlier ones instead of z-fighting at `z = 0`.
+const TILED_LAYER_Z_SPACING: f32 = 1.0;
+
+/// A parsed Tiled map, ready to be spawned as a [`Tilemap`] with chunked
+/// [`TileStorage<TileRenderData>`] children.
+///
+/// Produced by [`TiledMapLoader`] from a `.tmx` file on disk.
+#[derive(Asset, TypePath, Clone)]
+pub struct TiledMap {
+    /// The tile size Tiled reports for this map.
+    pub tile_display_size: UVec2,
+    /// The image referenced by the map's first tileset, loaded as-is. Images of
+    /// any further tilesets are not loaded.
+    pub tileset: Handle<Image>,
+    /// One entry per Tiled tile layer, already split into [`TILED_CHUNK_SIZE`] chunks.
+    pub layers: Vec<TiledLayer>,
+}
+
+impl fmt::Debug for TiledMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TiledMap")
+            .field("tile_display_size", &self.tile_display_size)
+            .field("layers", &self.layers.len())
+            .finish()
+    }
+}
+
+/// A single Tiled tile layer, pre-split into fixed-size chunks.
+#[derive(Clone)]
+pub struct TiledLayer {
+    /// Chunks making up this layer, in row-major chunk order.
+    pub chunks: Vec<TiledChunk>,
+}
+
+/// One chunk's worth of tile data cut from a Tiled layer.
+#[derive(Clone)]
+pub struct TiledChunk {
+    /// This chunk's origin within its layer, in tiles.
+    pub origin: UVec2,
+    /// The size, in tiles, of this chunk. Always [`TILED_CHUNK_SIZE`] except for
+    /// chunks trailing the edge of a layer whose size isn't a multiple of it.
+    pub size: UVec2,
+    /// Render data for each tile in the chunk, row-major.
+    pub tiles: Vec<TileRenderData>,
+}
+
+/// Loads Tiled (`.tmx`) maps, producing a [`TiledMap`] asset.
+///
+/// Only Tiled's XML map format is supported; the JSON equivalent (`.tmj`)
+/// isn't registered as an extension and will fail to load.
+///
+/// Spawn the result with [`TiledMapHandle`]; [`spawn_tiled_maps`] turns it into a
+/// [`Tilemap`] entity with chunked [`TilemapChunkRenderer`] children.
+#[derive(Default)]
+pub struct TiledMapLoader;
+
+impl AssetLoader for TiledMapLoader {
+    type Asset = TiledMap;
+    type Settings = ();
+    type Error = TiledMapLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut loader = tiled::Loader::new();
+        let map = loader
+            .load_tmx_map_from(std::io::Cursor::new(bytes), load_context.path())
+            .map_err(TiledMapLoaderError::Tiled)?;
+
+        let tile_display_size = UVec2::new(map.tile_width, map.tile_height);
+        let tileset_path = tileset_image_path(&map)?;
+        let tileset = load_context.load(tileset_path);
+
+        let layers = map
+            .layers()
+            .filter_map(|layer| layer.as_tile_layer())
+            .map(|layer| tiled_layer_to_chunks(&layer))
+            .collect();
+
+        Ok(TiledMap {
+            tile_display_size,
+            tileset,
+            layers,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tmx"]
+    }
+}
+
+fn tileset_image_path(map: &tiled::Map) -> Result<std::path::PathBuf, TiledMapLoaderError> {
+    let tileset = map
+        .tilesets()
+        .first()
+        .ok_or(TiledMapLoaderError::MissingTileset)?;
+    let image = tileset
+        .image
+        .as_ref()
+        .ok_or(TiledMapLoaderError::MissingTilesetImage)?;
+    Ok(image.source.clone())
+}
+
+fn tiled_layer_to_chunks(layer: &tiled::TileLayer) -> TiledLayer {
+    let width = layer.width().unwrap_or(0);
+    let height = layer.height().unwrap_or(0);
+    let opacity = layer.opacity();
+    let tint = layer.tint_color().map_or(Color::WHITE, tiled_color_to_bevy);
+
+    let chunks_x = width.div_ceil(TILED_CHUNK_SIZE.x);
+    let chunks_y = height.div_ceil(TILED_CHUNK_SIZE.y);
+
+    let mut chunks = Vec::with_capacity((chunks_x * chunks_y) as usize);
+    for chunk_y in 0..chunks_y {
+        for chunk_x in 0..chunks_x {
+            let origin = UVec2::new(chunk_x, chunk_y) * TILED_CHUNK_SIZE;
+            let size = (UVec2::new(width, height) - origin).min(TILED_CHUNK_SIZE);
+
+            let mut tiles = Vec::with_capacity((size.x * size.y) as usize);
+            for y in 0..size.y {
+                for x in 0..size.x {
+                    let tile = layer.get_tile((origin.x + x) as i32, (origin.y + y) as i32);
+                    tiles.push(tiled_tile_to_render_data(tile.as_ref(), opacity, tint));
+                }
+            }
+
+            chunks.push(TiledChunk {
+                origin,
+                size,
+                tiles,
+            });
+        }
+    }
+
+    TiledLayer { chunks }
+}
+
+fn tiled_tile_to_render_data(
+    tile: Option<&tiled::LayerTile>,
+    layer_opacity: f32,
+    layer_tint: Color,
+) -> TileRenderData {
+    let Some(tile) = tile else {
+        return TileRenderData {
+            visible: false,
+            ..default()
+        };
+    };
+
+    TileRenderData {
+        tileset_index: tile.id() as u16,
+        color: layer_tint.with_alpha(layer_tint.alpha() * layer_opacity),
+        visible: true,
+    }
+}
+
+fn tiled_color_to_bevy(color: tiled::Color) -> Color {
+    Color::srgba_u8(color.red, color.green, color.blue, color.alpha)
+}
+
+/// An error produced while loading a Tiled `.tmx` map.
+#[derive(Debug, Error)]
+pub enum TiledMapLoaderError {
+    /// An [`std::io::Error`] occurred while reading the map file.
+    #[error("could not read tiled map: {0}")]
+    Io(#[from] std::io::Error),
+    /// The `tiled` crate failed to parse the map.
+    #[error("could not parse tiled map: {0}")]
+    Tiled(tiled::Error),
+    /// The map did not reference any tileset.
+    #[error("tiled map does not reference a tileset")]
+    MissingTileset,
+    /// The map's tileset does not reference a single tileset image.
+    #[error(
+        "tiled map's tileset has no image source (image collection tilesets are not supported)"
+    )]
+    MissingTilesetImage,
+}
+
+/// Marker component that spawns a [`Tilemap`] from a loaded [`TiledMap`] asset.
+///
+/// Add this to an entity with a handle loaded via [`TiledMapLoader`]; once the
+/// asset finishes loading, [`spawn_tiled_maps`] populates the entity with a
+/// [`Tilemap`], a [`TilemapChunkRenderer`], and one child chunk entity per
+/// [`TiledChunk`].
+#[derive(Component, Deref, Reflect)]
+#[reflect(Component)]
+#[require(Transform)]
+pub struct TiledMapHandle(pub Handle<TiledMap>);
+
+/// Spawns [`Tilemap`] chunk hierarchies for newly loaded [`TiledMap`] assets.
+pub(super) fn spawn_tiled_maps(
+    query: Query<(Entity, &TiledMapHandle), Without<Tilemap>>,
+    maps: Res<Assets<TiledMap>>,
+    mut commands: Commands,
+) {
+    for (map_id, handle) in query {
+        let Some(map) = maps.get(&handle.0) else {
+            continue;
+        };
+
+        commands.entity(map_id).insert((
+            Tilemap {
+                tile_display_size: map.tile_display_size,
+                ..default()
+            },
+            TilemapChunkRenderer {
+                tileset: map.tileset.clone(),
+                alpha_mode: AlphaMode2d::Blend,
+                ..default()
+            },
+        ));
+
+        for (layer_index, layer) in map.layers.iter().enumerate() {
+            let layer_z = layer_index as f32 * TILED_LAYER_Z_SPACING;
+            for chunk in &layer.chunks {
+                let chunk_offset = (chunk.origin * map.tile_display_size).as_vec2();
+                commands.spawn((
+                    TileStorage::<TileRenderData> {
+                        size: chunk.size,
+                        tiles: chunk.tiles.clone(),
+                    },
+                    Transform::from_translation(chunk_offset.extend(layer_z)),
+                    ChildOf(map_id),
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use bevy_asset::{Assets, Handle, RenderAssetUsages};
+use bevy_derive::Deref;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::Without,
+    resource::Resource,
+    system::{Commands, Query, ResMut},
+};
+use bevy_image::{Image, TextureDimension, TextureFormat};
+use bevy_math::UVec2;
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{prelude::*, Reflect};
+use bevy_render::render_resource::Extent3d;
+use tracing::warn;
+
+use crate::AlphaMode2d;
+
+use super::{TilemapChunkRenderer, TilemapTopology};
+
+/// Caches tileset array textures assembled from loose tile images, keyed by the
+/// source handles that produced them, so re-spawning chunks with the same set of
+/// tiles reuses the array texture instead of re-stitching it.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct TilesetBuilderCache {
+    assembled: HashMap<Vec<Handle<Image>>, AssembledTileset>,
+}
+
+/// The result of stitching a set of individual tile images into a single
+/// array texture for use as [`TilemapChunkRenderer::tileset`](super::TilemapChunkRenderer::tileset).
+#[derive(Clone, Reflect)]
+pub struct AssembledTileset {
+    /// The stitched array texture, with one layer per source tile image.
+    pub tileset: Handle<Image>,
+    /// Maps each source tile handle to its layer index in [`Self::tileset`].
+    pub indices: HashMap<Handle<Image>, u16>,
+}
+
+impl AssembledTileset {
+    /// Looks up the `tileset_index` to use for a tile sourced from `handle`,
+    /// for populating [`TileRenderData::tileset_index`](super::TileRenderData::tileset_index).
+    pub fn tileset_index(&self, handle: &Handle<Image>) -> Option<u16> {
+        self.indices.get(handle).copied()
+    }
+}
+
+/// Error produced by [`TilesetBuilderCache::build_or_get`].
+#[derive(Debug, thiserror::Error)]
+pub enum TilesetBuildError {
+    /// No tile image handles were provided.
+    #[error("cannot build a tileset from an empty set of tile images")]
+    Empty,
+    /// A source handle did not resolve to a loaded image.
+    #[error("tile image {0:?} is not loaded")]
+    ImageNotLoaded(Handle<Image>),
+}
+
+impl TilesetBuilderCache {
+    /// Assembles `tiles` into a single layered array texture, or returns the
+    /// previously assembled tileset if this exact set of handles was built before.
+    ///
+    /// Tile images must share the same pixel dimensions; mismatched images are
+    /// padded (or cropped to their top-left corner, if larger) to the first
+    /// tile's size rather than rejected outright, so a folder load with a
+    /// stray odd-sized asset still produces a usable tileset.
+    pub fn build_or_get(
+        &mut self,
+        tiles: &[Handle<Image>],
+        images: &mut Assets<Image>,
+    ) -> Result<AssembledTileset, TilesetBuildError> {
+        if tiles.is_empty() {
+            return Err(TilesetBuildError::Empty);
+        }
+
+        let key = tiles.to_vec();
+        if let Some(assembled) = self.assembled.get(&key) {
+            return Ok(assembled.clone());
+        }
+
+        let assembled = build_tileset(tiles, images)?;
+        self.assembled.insert(key, assembled.clone());
+        Ok(assembled)
+    }
+}
+
+fn build_tileset(
+    tiles: &[Handle<Image>],
+    images: &mut Assets<Image>,
+) -> Result<AssembledTileset, TilesetBuildError> {
+    let tile_size = tiles
+        .first()
+        .and_then(|handle| images.get(handle))
+        .map(|image| image.size())
+        .ok_or_else(|| TilesetBuildError::ImageNotLoaded(tiles[0].clone()))?;
+
+    let format = TextureFormat::Rgba8UnormSrgb;
+    let layer_bytes = (tile_size.x * tile_size.y * 4) as usize;
+    let mut data = Vec::with_capacity(layer_bytes * tiles.len());
+    let mut indices = HashMap::with_capacity_and_hasher(tiles.len(), Default::default());
+
+    for (index, handle) in tiles.iter().enumerate() {
+        let Some(image) = images.get(handle) else {
+            return Err(TilesetBuildError::ImageNotLoaded(handle.clone()));
+        };
+
+        data.extend_from_slice(&fit_layer(image, tile_size, format));
+        indices.insert(handle.clone(), index as u16);
+    }
+
+    let tileset = Image::new(
+        Extent3d {
+            width: tile_size.x,
+            height: tile_size.y,
+            depth_or_array_layers: tiles.len() as u32,
+        },
+        TextureDimension::D2,
+        data,
+        format,
+        RenderAssetUsages::default(),
+    );
+
+    Ok(AssembledTileset {
+        tileset: images.add(tileset),
+        indices,
+    })
+}
+
+/// Add to a [`Tilemap`](bevy_sprite::Tilemap) entity instead of a pre-built
+/// [`TilemapChunkRenderer::tileset`] to have it assembled from a folder of
+/// individual tile images.
+///
+/// Once every handle in `tiles` has finished loading,
+/// [`assemble_loose_tilesets`] stitches them into an array texture via
+/// [`TilesetBuilderCache`] and inserts the resulting [`TilemapChunkRenderer`]
+/// (carrying `alpha_mode` and `topology` from here) alongside
+/// [`LooseTilesetIndices`].
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct LooseTileset {
+    /// The individual tile images to stitch into a single array texture.
+    pub tiles: Vec<Handle<Image>>,
+    /// The alpha mode the resulting [`TilemapChunkRenderer`] should use.
+    pub alpha_mode: AlphaMode2d,
+    /// The topology the resulting [`TilemapChunkRenderer`] should use.
+    pub topology: TilemapTopology,
+}
+
+/// The `tileset_index` each of a [`LooseTileset`]'s source handles was
+/// assigned, inserted by [`assemble_loose_tilesets`] once it stitches the
+/// array texture.
+#[derive(Component, Clone, Deref, Reflect)]
+#[reflect(Component)]
+pub struct LooseTilesetIndices(pub HashMap<Handle<Image>, u16>);
+
+/// Stitches every [`LooseTileset`] whose tile images have all finished
+/// loading into a [`TilemapChunkRenderer`], via [`TilesetBuilderCache`].
+///
+/// Runs every frame rather than just on `Added<LooseTileset>` since a
+/// folder load's images typically haven't finished loading yet when the
+/// component is first added.
+pub(super) fn assemble_loose_tilesets(
+    query: Query<(Entity, &LooseTileset), Without<LooseTilesetIndices>>,
+    mut cache: ResMut<TilesetBuilderCache>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    for (entity, loose) in &query {
+        match cache.build_or_get(&loose.tiles, &mut images) {
+            Ok(assembled) => {
+                commands.entity(entity).insert((
+                    TilemapChunkRenderer {
+                        tileset: assembled.tileset,
+                        alpha_mode: loose.alpha_mode,
+                        topology: loose.topology,
+                    },
+                    LooseTilesetIndices(assembled.indices),
+                ));
+            }
+            // Not every source image has loaded yet; try again next frame.
+            Err(TilesetBuildError::ImageNotLoaded(_)) => {}
+            Err(TilesetBuildError::Empty) => {
+                warn!("LooseTileset on entity {} has no tile images", entity);
+            }
+        }
+    }
+}
+
+/// Converts `image` to `format` and pads or top-left-crops it to
+/// `target_size`, returning one layer's worth of raw bytes.
+fn fit_layer(image: &Image, target_size: UVec2, format: TextureFormat) -> Vec<u8> {
+    let converted = image
+        .clone()
+        .convert(format)
+        .unwrap_or_else(|| image.clone());
+
+    if converted.size() == target_size {
+        return converted
+            .data
+            .clone()
+            .unwrap_or_else(|| vec![0; (target_size.x * target_size.y * 4) as usize]);
+    }
+
+    warn!(
+        "tile image size {:?} does not match tileset tile size {target_size:?}; padding/cropping to fit",
+        converted.size(),
+    );
+
+    let source_size = converted.size();
+    let source_data = converted.data.unwrap_or_default();
+    let mut out = vec![0u8; (target_size.x * target_size.y * 4) as usize];
+
+    let copy_width = source_size.x.min(target_size.x);
+    let copy_height = source_size.y.min(target_size.y);
+
+    for y in 0..copy_height {
+        let src_start = ((y * source_size.x) * 4) as usize;
+        let src_end = src_start + (copy_width * 4) as usize;
+        let dst_start = ((y * target_size.x) * 4) as usize;
+        let dst_end = dst_start + (copy_width * 4) as usize;
+        if src_end <= source_data.len() && dst_end <= out.len() {
+            out[dst_start..dst_end].copy_from_slice(&source_data[src_start..src_end]);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+    fn solid_image(size: UVec2, pixel: [u8; 4]) -> Image {
+        let data = pixel
+            .iter()
+            .copied()
+            .cycle()
+            .take((size.x * size.y * 4) as usize)
+            .collect();
+        Image::new(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            FORMAT,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn fit_layer_passes_through_a_matching_size() {
+        let image = solid_image(UVec2::new(2, 2), [1, 2, 3, 4]);
+        let bytes = fit_layer(&image, UVec2::new(2, 2), FORMAT);
+        assert_eq!(bytes, vec![1, 2, 3, 4].repeat(4));
+    }
+
+    #[test]
+    fn fit_layer_pads_a_smaller_image() {
+        let image = solid_image(UVec2::new(1, 1), [9, 9, 9, 9]);
+        let bytes = fit_layer(&image, UVec2::new(2, 2), FORMAT);
+
+        assert_eq!(bytes.len(), 2 * 2 * 4);
+        // Source pixel lands at (0, 0)...
+        assert_eq!(&bytes[0..4], &[9, 9, 9, 9]);
+        // ...and every pixel outside its footprint is left zeroed.
+        assert_eq!(&bytes[4..8], &[0, 0, 0, 0]);
+        assert_eq!(&bytes[8..12], &[0, 0, 0, 0]);
+        assert_eq!(&bytes[12..16], &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fit_layer_crops_a_larger_image_to_its_top_left_corner() {
+        let mut image = solid_image(UVec2::new(2, 2), [0, 0, 0, 0]);
+        // Distinguish each pixel so cropping to 1x1 can only pass by keeping
+        // the (0, 0) pixel specifically.
+        image.data = Some(vec![
+            1, 1, 1, 1, // (0, 0)
+            2, 2, 2, 2, // (1, 0)
+            3, 3, 3, 3, // (0, 1)
+            4, 4, 4, 4, // (1, 1)
+        ]);
+        let bytes = fit_layer(&image, UVec2::new(1, 1), FORMAT);
+
+        assert_eq!(bytes, vec![1, 1, 1, 1]);
+    }
+}
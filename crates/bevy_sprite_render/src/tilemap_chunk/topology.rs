@@ -0,0 +1,233 @@
+use bevy_math::{UVec2, Vec2, Vec3};
+use bevy_mesh::{Indices, Mesh, PrimitiveTopology};
+use bevy_reflect::Reflect;
+use bevy_render::render_asset::RenderAssetUsages;
+
+/// The grid layout a [`super::TilemapChunkRenderer`] lays its tiles out in.
+///
+/// This controls both the shape of the generated chunk mesh and how tile
+/// coordinates map to world space; it does not affect how tile data is
+/// stored, which remains a flat, row-major [`super::TileStorage`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Debug, Default, PartialEq, Hash)]
+pub enum TilemapTopology {
+    /// An axis-aligned square grid. Tile `(x, y)` sits at `(x, y) * tile_display_size`.
+    #[default]
+    Square,
+    /// A diamond-projected isometric grid.
+    Isometric,
+    /// A flat-top hexagonal grid with alternating rows offset horizontally.
+    HexRow,
+    /// A pointy-top hexagonal grid with alternating columns offset vertically.
+    HexColumn,
+}
+
+impl TilemapTopology {
+    /// The world-space offset of tile `coords`'s center, relative to the
+    /// chunk's origin, for a tile sized `tile_display_size`.
+    pub fn tile_center(self, coords: UVec2, tile_display_size: UVec2) -> Vec2 {
+        let size = tile_display_size.as_vec2();
+        let (x, y) = (coords.x as f32, coords.y as f32);
+        match self {
+            TilemapTopology::Square => Vec2::new(x * size.x, y * size.y),
+            TilemapTopology::Isometric => Vec2::new((x - y) * size.x * 0.5, (x + y) * size.y * 0.5),
+            TilemapTopology::HexRow => {
+                let row_offset = if coords.y % 2 == 1 { size.x * 0.5 } else { 0.0 };
+                Vec2::new(x * size.x + row_offset, y * size.y * 0.75)
+            }
+            TilemapTopology::HexColumn => {
+                let column_offset = if coords.x % 2 == 1 { size.y * 0.5 } else { 0.0 };
+                Vec2::new(x * size.x * 0.75, y * size.y + column_offset)
+            }
+        }
+    }
+
+    /// The inverse of [`Self::tile_center`]: which tile coordinate contains
+    /// `world_pos`, for a tile sized `tile_display_size`.
+    ///
+    /// For the staggered topologies this is an approximation that rounds to
+    /// the nearest tile center rather than performing exact hex/diamond
+    /// point-in-polygon tests; good enough for cursor picking and streaming
+    /// radius checks.
+    pub fn world_to_tile(self, world_pos: Vec2, tile_display_size: UVec2) -> UVec2 {
+        let size = tile_display_size.as_vec2();
+        let tile = match self {
+            TilemapTopology::Square => world_pos / size,
+            TilemapTopology::Isometric => Vec2::new(
+                world_pos.x / size.x + world_pos.y / size.y,
+                world_pos.y / size.y - world_pos.x / size.x,
+            ),
+            TilemapTopology::HexRow => {
+                let row = (world_pos.y / (size.y * 0.75)).round();
+                let row_offset = if row as i64 % 2 == 1 { 0.5 } else { 0.0 };
+                Vec2::new(world_pos.x / size.x - row_offset, row)
+            }
+            TilemapTopology::HexColumn => {
+                let column = (world_pos.x / (size.x * 0.75)).round();
+                let column_offset = if column as i64 % 2 == 1 { 0.5 } else { 0.0 };
+                Vec2::new(column, world_pos.y / size.y - column_offset)
+            }
+        };
+        tile.round().max(Vec2::ZERO).as_uvec2()
+    }
+
+    /// The unit-square corner offsets (as fractions of a tile's bounding box,
+    /// in `[-0.5, 0.5]`) used to build one tile's quad or hexagon, in winding
+    /// order, along with the triangle fan indices connecting them.
+    fn shape(self) -> (&'static [Vec2], &'static [u32]) {
+        const SQUARE: [Vec2; 4] = [
+            Vec2::new(-0.5, -0.5),
+            Vec2::new(0.5, -0.5),
+            Vec2::new(0.5, 0.5),
+            Vec2::new(-0.5, 0.5),
+        ];
+        const DIAMOND: [Vec2; 4] = [
+            Vec2::new(0.0, -0.5),
+            Vec2::new(0.5, 0.0),
+            Vec2::new(0.0, 0.5),
+            Vec2::new(-0.5, 0.0),
+        ];
+        const QUAD_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+        // A regular hexagon, flat-top for `HexRow` and pointy-top for
+        // `HexColumn`; `hex_corners` rotates the corners by 30 degrees for the
+        // pointy-top variant.
+        const HEX_INDICES: [u32; 12] = [0, 1, 2, 0, 2, 3, 0, 3, 4, 0, 4, 5];
+
+        match self {
+            TilemapTopology::Square => (&SQUARE, &QUAD_INDICES),
+            TilemapTopology::Isometric => (&DIAMOND, &QUAD_INDICES),
+            TilemapTopology::HexRow | TilemapTopology::HexColumn => {
+                (hex_corners(self), &HEX_INDICES)
+            }
+        }
+    }
+}
+
+fn hex_corners(topology: TilemapTopology) -> &'static [Vec2] {
+    use core::f32::consts::FRAC_PI_3;
+
+    // Lazily computed once; a hexagon's shape doesn't depend on tile size, so
+    // the unit corners are the same for every tile and can be shared.
+    static FLAT_TOP: std::sync::OnceLock<[Vec2; 6]> = std::sync::OnceLock::new();
+    static POINTY_TOP: std::sync::OnceLock<[Vec2; 6]> = std::sync::OnceLock::new();
+
+    let angle_offset = match topology {
+        TilemapTopology::HexRow => 0.0,
+        _ => FRAC_PI_3 * 0.5,
+    };
+    let cell = if matches!(topology, TilemapTopology::HexRow) {
+        &FLAT_TOP
+    } else {
+        &POINTY_TOP
+    };
+
+    cell.get_or_init(|| {
+        core::array::from_fn(|i| {
+            let angle = angle_offset + FRAC_PI_3 * i as f32;
+            Vec2::new(angle.cos(), angle.sin()) * 0.5
+        })
+    })
+}
+
+/// Builds the mesh for a whole chunk: one quad or hexagon per tile, laid out
+/// according to `topology`.
+///
+/// `ATTRIBUTE_UV_0` carries the usual intra-tile gradient (from the shape's
+/// corners) so [`super::TilemapChunkMaterial`]'s shader can keep sampling a
+/// tile's sprite across its own shape the same way it does for a single
+/// un-chunked quad. Since a chunk packs many tiles' worth of render data into
+/// one tile-data image, each corner also carries that tile's texel center in
+/// `ATTRIBUTE_UV_1`, constant across the tile, for the shader to look up
+/// which tile it's currently shading.
+pub(super) fn build_chunk_mesh(
+    topology: TilemapTopology,
+    storage_size: UVec2,
+    tile_display_size: UVec2,
+) -> Mesh {
+    let (corners, shape_indices) = topology.shape();
+
+    let tile_count = (storage_size.x * storage_size.y) as usize;
+    let mut positions = Vec::with_capacity(tile_count * corners.len());
+    let mut uvs = Vec::with_capacity(tile_count * corners.len());
+    let mut tile_uvs = Vec::with_capacity(tile_count * corners.len());
+    let mut indices = Vec::with_capacity(tile_count * shape_indices.len());
+
+    for y in 0..storage_size.y {
+        for x in 0..storage_size.x {
+            let coords = UVec2::new(x, y);
+            let center = topology.tile_center(coords, tile_display_size);
+            let base = positions.len() as u32;
+            let tile_uv = [
+                (x as f32 + 0.5) / storage_size.x as f32,
+                (y as f32 + 0.5) / storage_size.y as f32,
+            ];
+
+            for corner in corners {
+                let offset = *corner * tile_display_size.as_vec2();
+                positions.push(Vec3::new(center.x + offset.x, center.y + offset.y, 0.0));
+                // Corners span the full [0, 1] range across the tile's own
+                // shape so the shader can sample a sprite across it, exactly
+                // as it did for the single-quad mesh before chunking.
+                uvs.push([corner.x + 0.5, corner.y + 0.5]);
+                tile_uvs.push(tile_uv);
+            }
+
+            indices.extend(shape_indices.iter().map(|index| base + index));
+        }
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_1, tile_uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TILE_SIZE: UVec2 = UVec2::new(32, 16);
+
+    #[test]
+    fn square_tile_center_is_an_axis_aligned_grid() {
+        let center = TilemapTopology::Square.tile_center(UVec2::new(2, 3), TILE_SIZE);
+        assert_eq!(center, Vec2::new(64.0, 48.0));
+    }
+
+    #[test]
+    fn isometric_tile_center_is_diamond_projected() {
+        let center = TilemapTopology::Isometric.tile_center(UVec2::new(2, 3), TILE_SIZE);
+        assert_eq!(center, Vec2::new((2.0 - 3.0) * 16.0, (2.0 + 3.0) * 8.0));
+    }
+
+    #[test]
+    fn hex_row_offsets_alternate_by_row() {
+        let even_row = TilemapTopology::HexRow.tile_center(UVec2::new(1, 0), TILE_SIZE);
+        let odd_row = TilemapTopology::HexRow.tile_center(UVec2::new(1, 1), TILE_SIZE);
+        assert_eq!(even_row.x, 32.0);
+        assert_eq!(odd_row.x, 32.0 + TILE_SIZE.x as f32 * 0.5);
+    }
+
+    #[test]
+    fn hex_column_offsets_alternate_by_column() {
+        let even_column = TilemapTopology::HexColumn.tile_center(UVec2::new(0, 1), TILE_SIZE);
+        let odd_column = TilemapTopology::HexColumn.tile_center(UVec2::new(1, 1), TILE_SIZE);
+        assert_eq!(even_column.y, 16.0);
+        assert_eq!(odd_column.y, 16.0 + TILE_SIZE.y as f32 * 0.5);
+    }
+
+    #[test]
+    fn world_to_tile_is_the_inverse_of_tile_center_on_square() {
+        let coords = UVec2::new(4, 5);
+        let center = TilemapTopology::Square.tile_center(coords, TILE_SIZE);
+        assert_eq!(
+            TilemapTopology::Square.world_to_tile(center, TILE_SIZE),
+            coords
+        );
+    }
+}